@@ -0,0 +1,146 @@
+//! Minimal `Buf`/`MutBuf` cursor buffers, in the spirit of `bytes::Buf`,
+//! used to drain and fill non-blocking reads and writes without the
+//! caller having to track its own position into the backing slice.
+
+/// A readable buffer cursor: `bytes()` exposes the unread remainder and
+/// `advance` consumes it as bytes are copied out.
+pub trait Buf {
+    fn remaining(&self) -> usize;
+    fn bytes(&self) -> &[u8];
+    fn advance(&mut self, cnt: usize);
+
+    fn has_remaining(&self) -> bool {
+        self.remaining() > 0
+    }
+}
+
+/// A writable buffer cursor: `mut_bytes` exposes the unwritten remainder
+/// and `advance` marks bytes as written.
+pub trait MutBuf {
+    fn remaining(&self) -> usize;
+    unsafe fn mut_bytes(&mut self) -> &mut [u8];
+    unsafe fn advance(&mut self, cnt: usize);
+
+    fn has_remaining(&self) -> bool {
+        self.remaining() > 0
+    }
+}
+
+/// An owned, fixed-capacity byte buffer that can be flipped between write mode
+/// (`MutByteBuf`) and read mode (`ByteBuf`), mirroring the classic
+/// `ByteBuffer#flip` pattern.
+pub struct ByteBuf {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+/// The write-mode view of a `ByteBuf`, obtained from `ByteBuf::mut_with_capacity`
+/// or by `flip`-ping a drained `ByteBuf`.
+pub struct MutByteBuf {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl ByteBuf {
+    pub fn mut_with_capacity(capacity: usize) -> MutByteBuf {
+        MutByteBuf { buf: vec![0; capacity], pos: 0 }
+    }
+
+    /// Flips back to write mode, growing the backing `Vec` back out to its
+    /// original capacity so the next round of writes has the full buffer
+    /// to fill again, not just the slice that was read.
+    pub fn flip(self) -> MutByteBuf {
+        let mut buf = self.buf;
+        let cap = buf.capacity();
+        buf.resize(cap, 0);
+
+        MutByteBuf { buf: buf, pos: 0 }
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+
+        let b = self.buf[self.pos];
+        self.pos += 1;
+        Some(b)
+    }
+}
+
+impl Buf for ByteBuf {
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.pos += cnt;
+    }
+}
+
+impl MutByteBuf {
+    /// Flips to read mode: bytes already written (`[0, pos)`) become the
+    /// readable contents, and anything past `pos` that was never written
+    /// to is dropped rather than exposed as if it had been read.
+    pub fn flip(self) -> ByteBuf {
+        let mut buf = self.buf;
+        buf.truncate(self.pos);
+
+        ByteBuf { buf: buf, pos: 0 }
+    }
+}
+
+impl MutBuf for MutByteBuf {
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    unsafe fn mut_bytes(&mut self) -> &mut [u8] {
+        &mut self.buf[self.pos..]
+    }
+
+    unsafe fn advance(&mut self, cnt: usize) {
+        self.pos += cnt;
+    }
+}
+
+/// A read cursor over a borrowed slice, used by tests and callers
+/// that already own a fixed buffer (e.g. a `&'static str` message).
+pub struct SliceBuf<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SliceBuf<'a> {
+    pub fn wrap(buf: &'a [u8]) -> SliceBuf<'a> {
+        SliceBuf { buf: buf, pos: 0 }
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+
+        let b = self.buf[self.pos];
+        self.pos += 1;
+        Some(b)
+    }
+}
+
+impl<'a> Buf for SliceBuf<'a> {
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.pos += cnt;
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::ops::{BitOr, BitAnd, Sub};
+
+use token::Token;
+
+const READABLE: usize = 0b0001;
+const WRITABLE: usize = 0b0010;
+const ERROR:    usize = 0b0100;
+const HUP:      usize = 0b1000;
+
+/// A set of readiness event kinds.
+///
+/// `EventSet` replaces the old pair of `Handler::readable` / `Handler::writable`
+/// callbacks (and the `ReadHint` that rode along with `readable`) with a
+/// single bitset that can represent any combination of readable, writable,
+/// hup and error readiness delivered for one `Token` in one pass of the
+/// event loop. `Interest`, which describes what a caller *wants* to be
+/// notified about, is the same bitset viewed from the registration side.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct EventSet(usize);
+
+impl EventSet {
+    /// Returns an empty `EventSet`.
+    pub fn none() -> EventSet {
+        EventSet(0)
+    }
+
+    /// Returns an `EventSet` representing readable readiness.
+    pub fn readable() -> EventSet {
+        EventSet(READABLE)
+    }
+
+    /// Returns an `EventSet` representing writable readiness.
+    pub fn writable() -> EventSet {
+        EventSet(WRITABLE)
+    }
+
+    /// Returns an `EventSet` representing error readiness.
+    pub fn error() -> EventSet {
+        EventSet(ERROR)
+    }
+
+    /// Returns an `EventSet` representing hang-up readiness.
+    pub fn hup() -> EventSet {
+        EventSet(HUP)
+    }
+
+    /// Returns an `EventSet` containing all kinds of readiness.
+    pub fn all() -> EventSet {
+        EventSet(READABLE | WRITABLE | ERROR | HUP)
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == EventSet::none()
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.contains(EventSet::readable())
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.contains(EventSet::writable())
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.contains(EventSet::error())
+    }
+
+    pub fn is_hup(&self) -> bool {
+        self.contains(EventSet::hup())
+    }
+
+    pub fn insert(&mut self, other: EventSet) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: EventSet) {
+        self.0 &= !other.0;
+    }
+
+    pub fn contains(&self, other: EventSet) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitOr for EventSet {
+    type Output = EventSet;
+
+    fn bitor(self, other: EventSet) -> EventSet {
+        EventSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for EventSet {
+    type Output = EventSet;
+
+    fn bitand(self, other: EventSet) -> EventSet {
+        EventSet(self.0 & other.0)
+    }
+}
+
+impl Sub for EventSet {
+    type Output = EventSet;
+
+    fn sub(self, other: EventSet) -> EventSet {
+        EventSet(self.0 & !other.0)
+    }
+}
+
+impl fmt::Debug for EventSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut one = false;
+
+        for &(flag, name) in [(EventSet::readable(), "Readable"),
+                               (EventSet::writable(), "Writable"),
+                               (EventSet::error(), "Error"),
+                               (EventSet::hup(), "Hup")].iter() {
+            if self.contains(flag) {
+                if one { try!(write!(fmt, " | ")); }
+                try!(write!(fmt, "{}", name));
+                one = true;
+            }
+        }
+
+        if !one {
+            try!(write!(fmt, "(empty)"));
+        }
+
+        Ok(())
+    }
+}
+
+/// What a caller wants to be notified about when registering with an
+/// `EventLoop`.
+///
+/// Registration and readiness share the same vocabulary, so `Interest` is
+/// simply an alias for `EventSet`.
+pub type Interest = EventSet;
+
+const EDGE: usize = 0b001;
+const LEVEL: usize = 0b010;
+const ONESHOT: usize = 0b100;
+
+/// Options supplied when registering an `Evented` handle that control how
+/// the `EventLoop` delivers readiness notifications for it.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct PollOpt(usize);
+
+impl PollOpt {
+    pub fn edge() -> PollOpt {
+        PollOpt(EDGE)
+    }
+
+    pub fn level() -> PollOpt {
+        PollOpt(LEVEL)
+    }
+
+    pub fn oneshot() -> PollOpt {
+        PollOpt(ONESHOT)
+    }
+
+    pub fn is_edge(&self) -> bool {
+        self.contains(PollOpt::edge())
+    }
+
+    pub fn is_level(&self) -> bool {
+        self.contains(PollOpt::level())
+    }
+
+    pub fn is_oneshot(&self) -> bool {
+        self.contains(PollOpt::oneshot())
+    }
+
+    pub fn contains(&self, other: PollOpt) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitOr for PollOpt {
+    type Output = PollOpt;
+
+    fn bitor(self, other: PollOpt) -> PollOpt {
+        PollOpt(self.0 | other.0)
+    }
+}
+
+/// A readiness notification delivered by the OS selector for a single
+/// registered source.
+#[derive(Copy, Clone, Debug)]
+pub struct IoEvent {
+    token: Token,
+    events: EventSet,
+}
+
+impl IoEvent {
+    pub fn new(events: EventSet, token: Token) -> IoEvent {
+        IoEvent { token: token, events: events }
+    }
+
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    pub fn events(&self) -> EventSet {
+        self.events
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::mpsc;
+
+use event::{EventSet, IoEvent, Interest, PollOpt};
+use handler::Handler;
+use io::Evented;
+use sys;
+use token::Token;
+
+/// Tunables for an `EventLoop`. `EventLoop::new` uses the defaults; use
+/// `EventLoop::configured` to override them.
+#[derive(Copy, Clone, Debug)]
+pub struct EventLoopConfig {
+    pub io_poll_timeout_ms: usize,
+}
+
+impl Default for EventLoopConfig {
+    fn default() -> EventLoopConfig {
+        EventLoopConfig {
+            io_poll_timeout_ms: 1_000,
+        }
+    }
+}
+
+/// The other half of `EventLoop::channel()`; lets any thread wake the loop
+/// up with a `Handler::Message`.
+pub struct Sender<M> {
+    tx: mpsc::Sender<M>,
+}
+
+impl<M> Sender<M> {
+    pub fn send(&self, msg: M) -> Result<(), mpsc::SendError<M>> {
+        self.tx.send(msg)
+    }
+}
+
+impl<M> Clone for Sender<M> {
+    fn clone(&self) -> Sender<M> {
+        Sender { tx: self.tx.clone() }
+    }
+}
+
+/// Drives registered `Evented` handles, dispatching readiness and messages
+/// to a `Handler` until `shutdown()` is called. It has no timers, so
+/// `Handler::timeout` is never called.
+pub struct EventLoop<H: Handler> {
+    run: bool,
+    config: EventLoopConfig,
+    selector: sys::Selector,
+    events: Vec<IoEvent>,
+    // Last-delivered `EventSet` per registered `Token`, exposed read-only
+    // through `readiness()` so a handler can re-check "is there still
+    // buffered readiness?" without another syscall. Purely informational:
+    // it is updated by real OS events and dropped on `deregister`, and
+    // never by itself causes a `ready` dispatch. It's also cleared right
+    // after a token's `ready` call returns, since those events have now
+    // been handed to the handler, unless that call asked for `redeliver`:
+    // then the delivered events are kept for the next pass to hand over.
+    readiness: HashMap<Token, EventSet>,
+    // Tokens to actually call `Handler::ready` for on the next `run_once`.
+    // Populated each pass from the real OS events `select()` just
+    // returned, plus whatever a handler asked to see again via
+    // `redeliver` because it knows it didn't fully drain a source.
+    // Dispatch is driven from this set, not from `readiness`, so a token
+    // that is simply never reregistered (a listening socket, say) is not
+    // replayed forever just because its cache entry is still non-empty.
+    pending: HashSet<Token>,
+    notify_tx: mpsc::Sender<H::Message>,
+    notify_rx: mpsc::Receiver<H::Message>,
+}
+
+impl<H: Handler> EventLoop<H> {
+    pub fn new() -> io::Result<EventLoop<H>> {
+        EventLoop::configured(EventLoopConfig::default())
+    }
+
+    pub fn configured(config: EventLoopConfig) -> io::Result<EventLoop<H>> {
+        let (tx, rx) = mpsc::channel();
+
+        Ok(EventLoop {
+            run: false,
+            config: config,
+            selector: try!(sys::Selector::new()),
+            events: Vec::with_capacity(1024),
+            readiness: HashMap::new(),
+            pending: HashSet::new(),
+            notify_tx: tx,
+            notify_rx: rx,
+        })
+    }
+
+    /// Returns a handle that can be used from any thread to deliver a
+    /// `Handler::Message` via `Handler::notify`.
+    pub fn channel(&self) -> Sender<H::Message> {
+        Sender { tx: self.notify_tx.clone() }
+    }
+
+    /// Returns the readiness cached for `token`: inside `ready`, the events
+    /// being delivered; otherwise what the OS has reported since the
+    /// token's last `ready` call (plus anything kept for a `redeliver`).
+    /// Returns `EventSet::none()` if there is nothing, or if the token has
+    /// been dropped by a `deregister`. Lets a handler that only partially
+    /// drained a socket re-check "is there still buffered readability?"
+    /// without another syscall.
+    pub fn readiness(&self, token: Token) -> EventSet {
+        self.readiness.get(&token).cloned().unwrap_or(EventSet::none())
+    }
+
+    /// Asks the loop to call `Handler::ready` again for `token` on the
+    /// next `run_once`, with its last-known `readiness()`, even if no new
+    /// OS event arrives. Call this from inside `ready` when a short read
+    /// (or any other partial drain) means there may still be buffered
+    /// readiness left for this source, to avoid a lost wakeup under
+    /// edge-triggered registration.
+    pub fn redeliver(&mut self, token: Token) {
+        self.pending.insert(token);
+    }
+
+    pub fn register_opt<E: Evented>(&mut self, io: &E, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        try!(io.register(&mut self.selector, token, interest, opts));
+        // A fresh registration always starts with a clean slate, even if
+        // `token` was previously used (e.g. a `Slab` index recycled from
+        // a closed connection) and still has stale cached readiness.
+        self.readiness.insert(token, EventSet::none());
+        self.pending.remove(&token);
+        Ok(())
+    }
+
+    pub fn reregister<E: Evented>(&mut self, io: &E, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        io.reregister(&mut self.selector, token, interest, opts)
+    }
+
+    pub fn deregister<E: Evented>(&mut self, io: &E, token: Token) -> io::Result<()> {
+        try!(io.deregister(&mut self.selector));
+        self.readiness.remove(&token);
+        self.pending.remove(&token);
+        Ok(())
+    }
+
+    /// Stops `run` after the current iteration.
+    pub fn shutdown(&mut self) {
+        self.run = false;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run
+    }
+
+    pub fn run(&mut self, handler: &mut H) -> io::Result<()> {
+        self.run = true;
+
+        while self.run {
+            try!(self.run_once(handler, self.config.io_poll_timeout_ms));
+        }
+
+        Ok(())
+    }
+
+    pub fn run_once(&mut self, handler: &mut H, timeout_ms: usize) -> io::Result<()> {
+        match self.selector.select(&mut self.events, timeout_ms) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => handler.interrupted(self),
+            Err(e) => return Err(e),
+        }
+
+        // Coalesce this pass's OS events into the readiness cache rather
+        // than dispatching each one as it's seen: two `IoEvent`s for the
+        // same token in one `select()` update the cache twice but still
+        // produce a single `ready` call, queued by marking the token
+        // pending just once.
+        for i in 0..self.events.len() {
+            let event = self.events[i];
+            self.readiness.entry(event.token())
+                .or_insert(EventSet::none())
+                .insert(event.events());
+            self.pending.insert(event.token());
+        }
+
+        let dispatch: Vec<Token> = self.pending.drain().collect();
+
+        for token in dispatch {
+            let events = self.readiness(token);
+            handler.ready(self, token, events);
+
+            // `events` has now been delivered; acknowledge consumption by
+            // narrowing the cache back to empty so a later `readiness()`
+            // query reflects only activity that's arrived since, not
+            // readiness that was already handed to the handler. A token
+            // passed to `redeliver` during the call keeps `events`, so the
+            // next pass hands them over again. If the token was
+            // deregistered from within `ready`, leave it gone.
+            let redelivering = self.pending.contains(&token);
+
+            if let Some(cached) = self.readiness.get_mut(&token) {
+                *cached = if redelivering { events } else { EventSet::none() };
+            }
+        }
+
+        while let Ok(msg) = self.notify_rx.try_recv() {
+            handler.notify(self, msg);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use event::EventSet;
+use event_loop::EventLoop;
+use token::Token;
+
+/// A callback interface invoked by an `EventLoop` as it runs.
+///
+/// All methods have empty default implementations, so a handler only has to
+/// override the ones it cares about.
+pub trait Handler: Sized {
+    type Timeout;
+    type Message;
+
+    /// Invoked when a registered `Evented` handle becomes ready. `events`
+    /// may report any combination of readable, writable, hup and error
+    /// readiness for `token` observed during a single pass of the loop.
+    fn ready(&mut self, event_loop: &mut EventLoop<Self>, token: Token, events: EventSet) {
+        let _ = (event_loop, token, events);
+    }
+
+    /// Invoked when `EventLoop::channel()` delivers a message sent via
+    /// `Sender::send`.
+    fn notify(&mut self, event_loop: &mut EventLoop<Self>, msg: Self::Message) {
+        let _ = (event_loop, msg);
+    }
+
+    /// Timeout callback. `EventLoop` has no way to register a timeout and
+    /// never calls this method.
+    fn timeout(&mut self, event_loop: &mut EventLoop<Self>, timeout: Self::Timeout) {
+        let _ = (event_loop, timeout);
+    }
+
+    /// Invoked when the event loop was interrupted by a signal.
+    fn interrupted(&mut self, event_loop: &mut EventLoop<Self>) {
+        let _ = event_loop;
+    }
+}
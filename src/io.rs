@@ -0,0 +1,106 @@
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use buf::{Buf, MutBuf};
+use event::{Interest, PollOpt};
+use sys;
+use token::Token;
+
+/// A value that may be registered with an `EventLoop`'s selector.
+///
+/// Implementors hand the event loop their OS-level registration hooks;
+/// callers never talk to `Evented` directly and instead go through
+/// `EventLoop::register_opt` / `reregister` / `deregister`.
+pub trait Evented {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()>;
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()>;
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()>;
+}
+
+/// Non-blocking read, returning `Ok(None)` instead of an `EWOULDBLOCK` error.
+pub trait TryRead {
+    fn read(&mut self, buf: &mut MutBuf) -> io::Result<Option<usize>> {
+        let res = self.read_slice(unsafe { buf.mut_bytes() });
+
+        if let Ok(Some(cnt)) = res {
+            unsafe { buf.advance(cnt); }
+        }
+
+        res
+    }
+
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>>;
+}
+
+/// Non-blocking write, returning `Ok(None)` instead of an `EWOULDBLOCK` error.
+pub trait TryWrite {
+    fn write(&mut self, buf: &mut Buf) -> io::Result<Option<usize>> {
+        let res = self.write_slice(buf.bytes());
+
+        if let Ok(Some(cnt)) = res {
+            buf.advance(cnt);
+        }
+
+        res
+    }
+
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>>;
+}
+
+/// Translates an `io::Result` coming straight off a non-blocking syscall
+/// into the `Ok(None)`-on-`WouldBlock` convention used throughout mio.
+pub fn would_block<T>(res: io::Result<T>) -> io::Result<Option<T>> {
+    match res {
+        Ok(val) => Ok(Some(val)),
+        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Wraps an I/O handle, marking it as non-blocking and `Evented`.
+///
+/// `NonBlock<T>` itself only adds `Deref` to `T` and the `Evented`
+/// passthrough shared by every transport (`tcp`, `udp`, `unix`); each
+/// transport adds its read/write/send/recv methods in its own
+/// `impl NonBlock<T>` block.
+pub struct NonBlock<T> {
+    inner: T,
+}
+
+impl<T> NonBlock<T> {
+    pub fn new(inner: T) -> NonBlock<T> {
+        NonBlock { inner: inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Deref for NonBlock<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for NonBlock<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: Evented> Evented for NonBlock<T> {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.inner.register(selector, token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.inner.reregister(selector, token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        self.inner.deregister(selector)
+    }
+}
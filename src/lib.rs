@@ -0,0 +1,29 @@
+//! A fast, low-level I/O library for Rust focusing on non-blocking APIs and
+//! event notification for building high performance I/O apps with as little
+//! overhead as possible over the OS abstractions.
+
+#![crate_name = "mio"]
+#![crate_type = "lib"]
+
+#[macro_use]
+extern crate log;
+extern crate libc;
+
+pub use event::{EventSet, Interest, PollOpt, IoEvent};
+pub use event_loop::{EventLoop, EventLoopConfig};
+pub use handler::Handler;
+pub use io::{Evented, NonBlock, TryRead, TryWrite};
+pub use token::Token;
+
+pub mod buf;
+pub mod tcp;
+pub mod udp;
+pub mod unix;
+pub mod util;
+
+mod event;
+mod event_loop;
+mod handler;
+mod io;
+mod sys;
+mod token;
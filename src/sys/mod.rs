@@ -0,0 +1,5 @@
+#[cfg(unix)]
+pub use self::unix::{Io, Selector, net, read_raw, write_raw};
+
+#[cfg(unix)]
+mod unix;
@@ -0,0 +1,79 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+
+use libc;
+
+use io::{TryRead, TryWrite, would_block};
+
+/// A thin, `Drop`-closing wrapper around a raw file descriptor, shared by
+/// every unix transport (`tcp`, `udp`, `unix`) as the innermost layer that
+/// actually calls `read(2)`/`write(2)`.
+pub struct Io {
+    fd: RawFd,
+}
+
+impl Io {
+    pub fn new(fd: RawFd) -> Io {
+        Io { fd: fd }
+    }
+}
+
+impl AsRawFd for Io {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for Io {
+    unsafe fn from_raw_fd(fd: RawFd) -> Io {
+        Io::new(fd)
+    }
+}
+
+impl TryRead for Io {
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        read_raw(self.fd, buf)
+    }
+}
+
+impl TryWrite for Io {
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>> {
+        write_raw(self.fd, buf)
+    }
+}
+
+impl Drop for Io {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd); }
+    }
+}
+
+/// Reads directly from `fd`, independent of any `Io` wrapper owning it.
+///
+/// Used by `tcp::ReadHalf`, which only ever holds a raw fd shared with its
+/// `WriteHalf` sibling and so can't go through `Io`'s `&mut self` methods.
+pub fn read_raw(fd: RawFd, buf: &mut [u8]) -> io::Result<Option<usize>> {
+    let res = unsafe {
+        libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len())
+    };
+
+    would_block(cvt(res).map(|n| n as usize))
+}
+
+/// Writes directly to `fd`; the `WriteHalf` counterpart of `read_raw`.
+pub fn write_raw(fd: RawFd, buf: &[u8]) -> io::Result<Option<usize>> {
+    let res = unsafe {
+        libc::write(fd, buf.as_ptr() as *const libc::c_void, buf.len())
+    };
+
+    would_block(cvt(res).map(|n| n as usize))
+}
+
+/// Turns a `-1`-on-error libc return value into an `io::Result`.
+fn cvt(res: libc::ssize_t) -> io::Result<libc::ssize_t> {
+    if res < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(res)
+    }
+}
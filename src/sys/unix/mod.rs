@@ -0,0 +1,6 @@
+pub use self::io::{Io, read_raw, write_raw};
+pub use self::selector::Selector;
+
+mod io;
+mod selector;
+pub mod net;
@@ -0,0 +1,143 @@
+//! Small helpers for creating and configuring raw sockets, shared by the
+//! `tcp`, `udp` and `unix` transports.
+
+use std::io;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
+
+use libc;
+
+pub fn socket(family: libc::c_int, ty: libc::c_int) -> io::Result<RawFd> {
+    let fd = unsafe { libc::socket(family, ty, 0) };
+
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    set_nonblock(fd)?;
+    set_cloexec(fd)?;
+
+    Ok(fd)
+}
+
+pub fn set_nonblock(fd: RawFd) -> io::Result<()> {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL, 0) };
+
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    if unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+pub fn set_cloexec(fd: RawFd) -> io::Result<()> {
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+pub fn set_reuseaddr(fd: RawFd, val: bool) -> io::Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, val as libc::c_int)
+}
+
+pub fn setsockopt<T>(fd: RawFd, level: libc::c_int, name: libc::c_int, val: T) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(fd, level, name,
+                          &val as *const T as *const libc::c_void,
+                          mem::size_of::<T>() as libc::socklen_t)
+    };
+
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Converts a `std::net::SocketAddr` into a raw sockaddr suitable for
+/// `bind`/`connect`/`sendto`, returning the filled-in storage and its length.
+pub fn to_sockaddr(addr: &SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+
+    let len = match *addr {
+        SocketAddr::V4(ref a) => {
+            let sin = &mut storage as *mut _ as *mut libc::sockaddr_in;
+
+            unsafe {
+                (*sin).sin_family = libc::AF_INET as libc::sa_family_t;
+                (*sin).sin_port = a.port().to_be();
+                (*sin).sin_addr = libc::in_addr { s_addr: u32::from(*a.ip()).to_be() };
+            }
+
+            mem::size_of::<libc::sockaddr_in>() as libc::socklen_t
+        }
+        SocketAddr::V6(ref a) => {
+            let sin6 = &mut storage as *mut _ as *mut libc::sockaddr_in6;
+
+            unsafe {
+                (*sin6).sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                (*sin6).sin6_port = a.port().to_be();
+                (*sin6).sin6_addr = libc::in6_addr { s6_addr: a.ip().octets() };
+            }
+
+            mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t
+        }
+    };
+
+    (storage, len)
+}
+
+/// Converts a raw sockaddr, as filled in by `getsockname`/`getpeername`/
+/// `recvfrom`, back into a `std::net::SocketAddr`.
+pub unsafe fn from_sockaddr(storage: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match storage.ss_family as libc::c_int {
+        libc::AF_INET => {
+            let sa = &*(storage as *const _ as *const libc::sockaddr_in);
+            let ip = Ipv4Addr::from(u32::from_be(sa.sin_addr.s_addr));
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, u16::from_be(sa.sin_port))))
+        }
+        libc::AF_INET6 => {
+            let sa = &*(storage as *const _ as *const libc::sockaddr_in6);
+            let ip = Ipv6Addr::from(sa.sin6_addr.s6_addr);
+            Ok(SocketAddr::V6(SocketAddrV6::new(ip, u16::from_be(sa.sin6_port), sa.sin6_flowinfo, sa.sin6_scope_id)))
+        }
+        _ => Err(io::Error::new(io::ErrorKind::Other, "unsupported address family")),
+    }
+}
+
+pub fn peer_addr(fd: RawFd) -> io::Result<SocketAddr> {
+    unsafe {
+        let mut storage: libc::sockaddr_storage = mem::zeroed();
+        let mut len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        let ret = libc::getpeername(fd, &mut storage as *mut _ as *mut libc::sockaddr, &mut len);
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        from_sockaddr(&storage)
+    }
+}
+
+pub fn local_addr(fd: RawFd) -> io::Result<SocketAddr> {
+    unsafe {
+        let mut storage: libc::sockaddr_storage = mem::zeroed();
+        let mut len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        let ret = libc::getsockname(fd, &mut storage as *mut _ as *mut libc::sockaddr, &mut len);
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        from_sockaddr(&storage)
+    }
+}
@@ -0,0 +1,137 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use libc;
+
+use event::{EventSet, IoEvent, PollOpt};
+use token::Token;
+
+/// A thin wrapper around an `epoll` instance, used by `EventLoop` to turn
+/// OS readiness notifications into `IoEvent`s.
+pub struct Selector {
+    epfd: RawFd,
+}
+
+impl Selector {
+    pub fn new() -> io::Result<Selector> {
+        let epfd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+
+        if epfd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Selector { epfd: epfd })
+    }
+
+    pub fn select(&mut self, evts: &mut Vec<IoEvent>, timeout_ms: usize) -> io::Result<()> {
+        evts.clear();
+
+        let mut raw: [libc::epoll_event; 1024] = unsafe { ::std::mem::zeroed() };
+
+        let n = unsafe {
+            libc::epoll_wait(self.epfd, raw.as_mut_ptr(), raw.len() as i32, timeout_ms as i32)
+        };
+
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        for event in &raw[..n as usize] {
+            evts.push(IoEvent::new(from_raw_events(event.events), Token(event.u64 as usize)));
+        }
+
+        Ok(())
+    }
+
+    pub fn register(&mut self, fd: RawFd, token: Token, interest: EventSet, opts: PollOpt) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_ADD, fd, token, interest, opts)
+    }
+
+    pub fn reregister(&mut self, fd: RawFd, token: Token, interest: EventSet, opts: PollOpt) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_MOD, fd, token, interest, opts)
+    }
+
+    pub fn deregister(&mut self, fd: RawFd) -> io::Result<()> {
+        let mut info: libc::epoll_event = unsafe { ::std::mem::zeroed() };
+
+        let ret = unsafe { libc::epoll_ctl(self.epfd, libc::EPOLL_CTL_DEL, fd, &mut info) };
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    fn ctl(&mut self, op: libc::c_int, fd: RawFd, token: Token, interest: EventSet, opts: PollOpt) -> io::Result<()> {
+        let mut info = libc::epoll_event {
+            events: to_raw_events(interest, opts),
+            u64: token.as_usize() as u64,
+        };
+
+        let ret = unsafe { libc::epoll_ctl(self.epfd, op, fd, &mut info) };
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
+
+impl AsRawFd for Selector {
+    fn as_raw_fd(&self) -> RawFd {
+        self.epfd
+    }
+}
+
+impl Drop for Selector {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.epfd); }
+    }
+}
+
+fn to_raw_events(interest: EventSet, opts: PollOpt) -> u32 {
+    let mut events = 0;
+
+    if interest.is_readable() {
+        events |= libc::EPOLLIN;
+    }
+
+    if interest.is_writable() {
+        events |= libc::EPOLLOUT;
+    }
+
+    if opts.is_edge() {
+        events |= libc::EPOLLET;
+    }
+
+    if opts.is_oneshot() {
+        events |= libc::EPOLLONESHOT;
+    }
+
+    events as u32
+}
+
+fn from_raw_events(events: u32) -> EventSet {
+    let mut result = EventSet::none();
+    let events = events as libc::c_int;
+
+    if events & libc::EPOLLIN != 0 {
+        result.insert(EventSet::readable());
+    }
+
+    if events & libc::EPOLLOUT != 0 {
+        result.insert(EventSet::writable());
+    }
+
+    if events & libc::EPOLLHUP != 0 {
+        result.insert(EventSet::hup());
+    }
+
+    if events & libc::EPOLLERR != 0 {
+        result.insert(EventSet::error());
+    }
+
+    result
+}
@@ -0,0 +1,420 @@
+//! TCP sockets, registerable with an `EventLoop` and read/written through
+//! the non-blocking `TryRead`/`TryWrite` convention (`Ok(None)` on
+//! `WOULDBLOCK` instead of an error).
+
+use std::cell::Cell;
+use std::io;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::rc::Rc;
+
+use libc;
+
+use buf::{Buf, MutBuf};
+use event::{Interest, PollOpt};
+use io::{Evented, NonBlock, TryRead, TryWrite, would_block};
+use sys::{self, net};
+use token::Token;
+
+/// Creates an unbound, unconnected IPv4 TCP socket.
+pub fn v4() -> io::Result<NonBlock<TcpSocket>> {
+    let fd = try!(net::socket(libc::AF_INET, libc::SOCK_STREAM));
+    Ok(NonBlock::new(TcpSocket { io: sys::Io::new(fd) }))
+}
+
+/// Creates an unbound, unconnected IPv6 TCP socket.
+pub fn v6() -> io::Result<NonBlock<TcpSocket>> {
+    let fd = try!(net::socket(libc::AF_INET6, libc::SOCK_STREAM));
+    Ok(NonBlock::new(TcpSocket { io: sys::Io::new(fd) }))
+}
+
+/// An unconnected TCP socket, used to configure options and then either
+/// `connect` (becoming a `TcpStream`) or `bind` + `listen` (becoming a
+/// `TcpListener`).
+pub struct TcpSocket {
+    io: sys::Io,
+}
+
+impl NonBlock<TcpSocket> {
+    pub fn set_reuseaddr(&self, val: bool) -> io::Result<()> {
+        net::set_reuseaddr(self.as_raw_fd(), val)
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (sa, sa_len) = net::to_sockaddr(addr);
+
+        let ret = unsafe {
+            libc::bind(self.as_raw_fd(), &sa as *const _ as *const libc::sockaddr, sa_len)
+        };
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<(NonBlock<TcpStream>, bool)> {
+        let (sa, sa_len) = net::to_sockaddr(addr);
+
+        let ret = unsafe {
+            libc::connect(self.as_raw_fd(), &sa as *const _ as *const libc::sockaddr, sa_len)
+        };
+
+        let complete = if ret < 0 {
+            let err = io::Error::last_os_error();
+
+            if err.raw_os_error() != Some(libc::EINPROGRESS) {
+                return Err(err);
+            }
+
+            false
+        } else {
+            true
+        };
+
+        Ok((NonBlock::new(TcpStream { io: self.into_inner().io }), complete))
+    }
+
+    pub fn listen(self, backlog: i32) -> io::Result<NonBlock<TcpListener>> {
+        let ret = unsafe { libc::listen(self.as_raw_fd(), backlog) };
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(NonBlock::new(TcpListener { io: self.into_inner().io }))
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl Evented for TcpSocket {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
+
+/// A connected, non-blocking TCP stream.
+pub struct TcpStream {
+    io: sys::Io,
+}
+
+impl TryRead for TcpStream {
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        self.io.read_slice(buf)
+    }
+}
+
+impl TryWrite for TcpStream {
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>> {
+        self.io.write_slice(buf)
+    }
+}
+
+impl NonBlock<TcpStream> {
+    pub fn read(&mut self, buf: &mut MutBuf) -> io::Result<Option<usize>> {
+        TryRead::read(&mut **self, buf)
+    }
+
+    pub fn write(&mut self, buf: &mut Buf) -> io::Result<Option<usize>> {
+        TryWrite::write(&mut **self, buf)
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        net::peer_addr(self.as_raw_fd())
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        net::local_addr(self.as_raw_fd())
+    }
+
+    /// Splits the stream into owned read and write halves that can be
+    /// moved into separate state machines while still sharing the
+    /// underlying fd. Both halves remain independently registerable; each
+    /// `register`/`reregister` call only updates that half's desired
+    /// `Interest`, and the two are unioned before talking to the selector
+    /// so reregistering one half never clobbers the other's readiness.
+    pub fn split(self) -> (ReadHalf, WriteHalf) {
+        let TcpStream { io } = self.into_inner();
+        let fd = io.as_raw_fd();
+
+        // `Shared` takes over closing the fd; forget `io` so it doesn't
+        // also close it when dropped here.
+        ::std::mem::forget(io);
+
+        let shared = Rc::new(Shared {
+            fd: fd,
+            read_interest: Cell::new(Interest::none()),
+            write_interest: Cell::new(Interest::none()),
+        });
+
+        (ReadHalf { shared: shared.clone() }, WriteHalf { shared: shared })
+    }
+
+    /// Borrows the stream as independent read and write halves for the
+    /// duration of the borrow, without giving up ownership the way
+    /// `split` does. Like `split`, the two halves union their `Interest`
+    /// before talking to the selector, so reregistering one never clobbers
+    /// the other's readiness.
+    pub fn split_mut(&mut self) -> (ReadHalfMut, WriteHalfMut) {
+        let fd = self.as_raw_fd();
+
+        let shared = Rc::new(SplitMutShared {
+            read_interest: Cell::new(Interest::none()),
+            write_interest: Cell::new(Interest::none()),
+        });
+
+        (ReadHalfMut { fd: fd, shared: shared.clone(), marker: PhantomData },
+         WriteHalfMut { fd: fd, shared: shared, marker: PhantomData })
+    }
+}
+
+impl AsRawFd for TcpStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl FromRawFd for TcpStream {
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpStream {
+        TcpStream { io: sys::Io::new(fd) }
+    }
+}
+
+impl Evented for TcpStream {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
+
+/// The fd and bookkeeping shared by a `split()` stream's `ReadHalf` and
+/// `WriteHalf`. Each half tracks only its own desired `Interest`; the two
+/// are unioned whenever either half (re)registers, so neither half's
+/// edge-triggered reregistration can clobber readiness the other half is
+/// still waiting on.
+struct Shared {
+    fd: RawFd,
+    read_interest: Cell<Interest>,
+    write_interest: Cell<Interest>,
+}
+
+impl Shared {
+    fn combined_interest(&self) -> Interest {
+        self.read_interest.get() | self.write_interest.get()
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd); }
+    }
+}
+
+/// The read-only half of a `split()` `NonBlock<TcpStream>`.
+pub struct ReadHalf {
+    shared: Rc<Shared>,
+}
+
+impl TryRead for ReadHalf {
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        sys::read_raw(self.shared.fd, buf)
+    }
+}
+
+impl ReadHalf {
+    pub fn read(&mut self, buf: &mut MutBuf) -> io::Result<Option<usize>> {
+        TryRead::read(self, buf)
+    }
+}
+
+impl Evented for ReadHalf {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.read_interest.set(interest);
+        selector.register(self.shared.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.read_interest.set(interest);
+        selector.reregister(self.shared.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        self.shared.read_interest.set(Interest::none());
+        selector.deregister(self.shared.fd)
+    }
+}
+
+/// The write-only half of a `split()` `NonBlock<TcpStream>`.
+pub struct WriteHalf {
+    shared: Rc<Shared>,
+}
+
+impl TryWrite for WriteHalf {
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>> {
+        sys::write_raw(self.shared.fd, buf)
+    }
+}
+
+impl WriteHalf {
+    pub fn write(&mut self, buf: &mut Buf) -> io::Result<Option<usize>> {
+        TryWrite::write(self, buf)
+    }
+}
+
+impl Evented for WriteHalf {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.write_interest.set(interest);
+        selector.register(self.shared.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.write_interest.set(interest);
+        selector.reregister(self.shared.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        self.shared.write_interest.set(Interest::none());
+        selector.deregister(self.shared.fd)
+    }
+}
+
+/// The bookkeeping shared by a `split_mut()` borrow's `ReadHalfMut` and
+/// `WriteHalfMut`. Mirrors `Shared`, minus fd ownership: `split_mut` never
+/// takes the fd away from the `TcpStream` it borrows from, so there's
+/// nothing here for `Drop` to close.
+struct SplitMutShared {
+    read_interest: Cell<Interest>,
+    write_interest: Cell<Interest>,
+}
+
+impl SplitMutShared {
+    fn combined_interest(&self) -> Interest {
+        self.read_interest.get() | self.write_interest.get()
+    }
+}
+
+/// A borrowed, read-only view into a `TcpStream`, returned by `split_mut`.
+pub struct ReadHalfMut<'a> {
+    fd: RawFd,
+    shared: Rc<SplitMutShared>,
+    marker: PhantomData<&'a mut TcpStream>,
+}
+
+impl<'a> TryRead for ReadHalfMut<'a> {
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        sys::read_raw(self.fd, buf)
+    }
+}
+
+impl<'a> Evented for ReadHalfMut<'a> {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.read_interest.set(interest);
+        selector.register(self.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.read_interest.set(interest);
+        selector.reregister(self.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        self.shared.read_interest.set(Interest::none());
+        selector.deregister(self.fd)
+    }
+}
+
+/// A borrowed, write-only view into a `TcpStream`, returned by `split_mut`.
+pub struct WriteHalfMut<'a> {
+    fd: RawFd,
+    shared: Rc<SplitMutShared>,
+    marker: PhantomData<&'a mut TcpStream>,
+}
+
+impl<'a> TryWrite for WriteHalfMut<'a> {
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>> {
+        sys::write_raw(self.fd, buf)
+    }
+}
+
+impl<'a> Evented for WriteHalfMut<'a> {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.write_interest.set(interest);
+        selector.register(self.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        self.shared.write_interest.set(interest);
+        selector.reregister(self.fd, token, self.shared.combined_interest(), opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        self.shared.write_interest.set(Interest::none());
+        selector.deregister(self.fd)
+    }
+}
+
+/// A non-blocking TCP listening socket.
+pub struct TcpListener {
+    io: sys::Io,
+}
+
+impl NonBlock<TcpListener> {
+    pub fn accept(&self) -> io::Result<Option<NonBlock<TcpStream>>> {
+        let fd = unsafe {
+            libc::accept(self.as_raw_fd(), ::std::ptr::null_mut(), ::std::ptr::null_mut())
+        };
+
+        let fd = match would_block(if fd < 0 { Err(io::Error::last_os_error()) } else { Ok(fd) }) {
+            Ok(Some(fd)) => fd,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        try!(net::set_nonblock(fd));
+        try!(net::set_cloexec(fd));
+
+        Ok(Some(NonBlock::new(unsafe { TcpStream::from_raw_fd(fd) })))
+    }
+}
+
+impl AsRawFd for TcpListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl Evented for TcpListener {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
@@ -0,0 +1,25 @@
+/// Tokens are used as an associative index for registering an `Evented`
+/// handle with an `EventLoop`. Most `EventLoop` operations take a `Token`
+/// argument and use it to identify which handle an event or action applies
+/// to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Token(pub usize);
+
+impl Token {
+    #[inline]
+    pub fn as_usize(&self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for Token {
+    fn from(val: usize) -> Token {
+        Token(val)
+    }
+}
+
+impl From<Token> for usize {
+    fn from(val: Token) -> usize {
+        val.0
+    }
+}
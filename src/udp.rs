@@ -0,0 +1,160 @@
+//! UDP sockets, registerable with an `EventLoop` exactly like `mio::tcp`,
+//! using `send_to`/`recv_from` in place of `write`/`read` but sharing the
+//! same `Ok(None)`-on-`WOULDBLOCK` convention.
+
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use libc;
+
+use buf::{Buf, MutBuf};
+use event::{Interest, PollOpt};
+use io::{Evented, NonBlock, would_block};
+use sys::{self, net};
+use token::Token;
+
+/// Creates a non-blocking, unbound IPv4 UDP socket.
+pub fn v4() -> io::Result<NonBlock<UdpSocket>> {
+    let fd = try!(net::socket(libc::AF_INET, libc::SOCK_DGRAM));
+    Ok(NonBlock::new(UdpSocket { io: sys::Io::new(fd) }))
+}
+
+/// Creates a non-blocking, unbound IPv6 UDP socket.
+pub fn v6() -> io::Result<NonBlock<UdpSocket>> {
+    let fd = try!(net::socket(libc::AF_INET6, libc::SOCK_DGRAM));
+    Ok(NonBlock::new(UdpSocket { io: sys::Io::new(fd) }))
+}
+
+/// A non-blocking UDP datagram socket.
+pub struct UdpSocket {
+    io: sys::Io,
+}
+
+impl NonBlock<UdpSocket> {
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (sa, sa_len) = net::to_sockaddr(addr);
+
+        let ret = unsafe {
+            libc::bind(self.as_raw_fd(), &sa as *const _ as *const libc::sockaddr, sa_len)
+        };
+
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        net::local_addr(self.as_raw_fd())
+    }
+
+    pub fn set_broadcast(&self, val: bool) -> io::Result<()> {
+        net::setsockopt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_BROADCAST, val as libc::c_int)
+    }
+
+    pub fn set_multicast_loop(&self, val: bool) -> io::Result<()> {
+        net::setsockopt(self.as_raw_fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_LOOP, val as libc::c_int)
+    }
+
+    pub fn join_multicast(&self, multi: &::std::net::Ipv4Addr, iface: &::std::net::Ipv4Addr) -> io::Result<()> {
+        let req = libc::ip_mreq {
+            imr_multiaddr: libc::in_addr { s_addr: u32::from(*multi).to_be() },
+            imr_interface: libc::in_addr { s_addr: u32::from(*iface).to_be() },
+        };
+
+        net::setsockopt(self.as_raw_fd(), libc::IPPROTO_IP, libc::IP_ADD_MEMBERSHIP, req)
+    }
+
+    pub fn leave_multicast(&self, multi: &::std::net::Ipv4Addr, iface: &::std::net::Ipv4Addr) -> io::Result<()> {
+        let req = libc::ip_mreq {
+            imr_multiaddr: libc::in_addr { s_addr: u32::from(*multi).to_be() },
+            imr_interface: libc::in_addr { s_addr: u32::from(*iface).to_be() },
+        };
+
+        net::setsockopt(self.as_raw_fd(), libc::IPPROTO_IP, libc::IP_DROP_MEMBERSHIP, req)
+    }
+
+    /// Sends the remainder of `buf` to `target`, returning `Ok(None)` if the
+    /// send would block (the caller should retry once writable).
+    pub fn send_to(&self, buf: &mut Buf, target: &SocketAddr) -> io::Result<Option<usize>> {
+        let (sa, sa_len) = net::to_sockaddr(target);
+
+        let res = unsafe {
+            libc::sendto(self.as_raw_fd(),
+                         buf.bytes().as_ptr() as *const libc::c_void,
+                         buf.bytes().len(),
+                         0,
+                         &sa as *const _ as *const libc::sockaddr,
+                         sa_len)
+        };
+
+        let written = would_block(if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        });
+
+        if let Ok(Some(cnt)) = written {
+            buf.advance(cnt);
+        }
+
+        written
+    }
+
+    /// Fills `buf` with a single datagram, returning the sender's address,
+    /// or `Ok(None)` if no datagram is available yet.
+    pub fn recv_from(&self, buf: &mut MutBuf) -> io::Result<Option<SocketAddr>> {
+        use std::mem;
+
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut sa_len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        let dst = unsafe { buf.mut_bytes() };
+
+        let res = unsafe {
+            libc::recvfrom(self.as_raw_fd(),
+                           dst.as_mut_ptr() as *mut libc::c_void,
+                           dst.len(),
+                           0,
+                           &mut storage as *mut _ as *mut libc::sockaddr,
+                           &mut sa_len)
+        };
+
+        let read = would_block(if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        });
+
+        match read {
+            Ok(Some(cnt)) => {
+                unsafe { buf.advance(cnt); }
+                Ok(Some(try!(unsafe { net::from_sockaddr(&storage) })))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl AsRawFd for UdpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl Evented for UdpSocket {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
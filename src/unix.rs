@@ -0,0 +1,343 @@
+//! Unix domain sockets, registerable with an `EventLoop` and read/written
+//! through the same `TryRead`/`TryWrite`/`Evented` conventions as
+//! `mio::tcp`, for same-host IPC without the overhead of the TCP stack.
+
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::path::Path;
+
+use libc;
+
+use buf::{Buf, MutBuf};
+use event::{Interest, PollOpt};
+use io::{Evented, NonBlock, TryRead, TryWrite, would_block};
+use sys::{self, net};
+use token::Token;
+
+fn unix_socket(ty: libc::c_int) -> io::Result<RawFd> {
+    net::socket(libc::AF_UNIX, ty)
+}
+
+fn to_sockaddr_un(path: &Path) -> io::Result<(libc::sockaddr_un, libc::socklen_t)> {
+    let bytes = path.to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path is not valid UTF-8"))?
+        .as_bytes();
+
+    if bytes.len() >= 108 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "path too long for sockaddr_un"));
+    }
+
+    let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+    addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+    for (dst, src) in addr.sun_path.iter_mut().zip(bytes.iter()) {
+        *dst = *src as libc::c_char;
+    }
+
+    let len = (mem::size_of::<libc::sa_family_t>() + bytes.len() + 1) as libc::socklen_t;
+    Ok((addr, len))
+}
+
+/// Connects to a Unix domain stream socket listening at `path`.
+pub fn connect(path: &Path) -> io::Result<NonBlock<UnixStream>> {
+    let fd = try!(unix_socket(libc::SOCK_STREAM));
+    let (addr, addr_len) = try!(to_sockaddr_un(path));
+
+    let ret = unsafe {
+        libc::connect(fd, &addr as *const _ as *const libc::sockaddr, addr_len)
+    };
+
+    if ret < 0 {
+        let err = io::Error::last_os_error();
+
+        if err.raw_os_error() != Some(libc::EINPROGRESS) {
+            return Err(err);
+        }
+    }
+
+    Ok(NonBlock::new(UnixStream { io: sys::Io::new(fd) }))
+}
+
+/// Binds and listens on `path`, removing any stale socket file first.
+pub fn bind(path: &Path) -> io::Result<NonBlock<UnixListener>> {
+    let _ = ::std::fs::remove_file(path);
+
+    let fd = try!(unix_socket(libc::SOCK_STREAM));
+    let (addr, addr_len) = try!(to_sockaddr_un(path));
+
+    let ret = unsafe {
+        libc::bind(fd, &addr as *const _ as *const libc::sockaddr, addr_len)
+    };
+
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    if unsafe { libc::listen(fd, 128) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(NonBlock::new(UnixListener { io: sys::Io::new(fd) }))
+}
+
+/// A connected, non-blocking Unix domain stream socket.
+pub struct UnixStream {
+    io: sys::Io,
+}
+
+impl TryRead for UnixStream {
+    fn read_slice(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        self.io.read_slice(buf)
+    }
+}
+
+impl TryWrite for UnixStream {
+    fn write_slice(&mut self, buf: &[u8]) -> io::Result<Option<usize>> {
+        self.io.write_slice(buf)
+    }
+}
+
+impl NonBlock<UnixStream> {
+    /// Sends `fd` to the peer over `SCM_RIGHTS`, along with a single byte
+    /// of ordinary payload (some platforms refuse to carry ancillary data
+    /// on an otherwise-empty message).
+    pub fn send_fd(&self, fd: RawFd) -> io::Result<()> {
+        unsafe {
+            let mut byte = 0u8;
+            let mut iov = libc::iovec { iov_base: &mut byte as *mut _ as *mut libc::c_void, iov_len: 1 };
+            let mut cmsg_buf = [0u8; 64];
+
+            let mut msg: libc::msghdr = mem::zeroed();
+            msg.msg_iov = &mut iov;
+            msg.msg_iovlen = 1;
+            msg.msg_control = cmsg_buf.as_mut_ptr() as *mut libc::c_void;
+            msg.msg_controllen = cmsg_len();
+
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<RawFd>() as libc::c_uint) as libc::size_t;
+
+            *(libc::CMSG_DATA(cmsg) as *mut RawFd) = fd;
+
+            let ret = libc::sendmsg(self.as_raw_fd(), &msg, 0);
+
+            if ret < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Receives a file descriptor previously handed over with `send_fd`.
+    pub fn recv_fd(&self) -> io::Result<Option<RawFd>> {
+        unsafe {
+            let mut byte = 0u8;
+            let mut iov = libc::iovec { iov_base: &mut byte as *mut _ as *mut libc::c_void, iov_len: 1 };
+            let mut cmsg_buf = [0u8; 64];
+
+            let mut msg: libc::msghdr = mem::zeroed();
+            msg.msg_iov = &mut iov;
+            msg.msg_iovlen = 1;
+            msg.msg_control = cmsg_buf.as_mut_ptr() as *mut libc::c_void;
+            msg.msg_controllen = cmsg_len();
+
+            let ret = libc::recvmsg(self.as_raw_fd(), &mut msg, 0);
+
+            if try!(would_block(if ret < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(ret)
+            })).is_none() {
+                return Ok(None);
+            }
+
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+
+            if cmsg.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(*(libc::CMSG_DATA(cmsg) as *const RawFd)))
+        }
+    }
+}
+
+fn cmsg_len() -> libc::size_t {
+    unsafe { libc::CMSG_SPACE(mem::size_of::<RawFd>() as libc::c_uint) as libc::size_t }
+}
+
+impl AsRawFd for UnixStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl FromRawFd for UnixStream {
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixStream {
+        UnixStream { io: sys::Io::new(fd) }
+    }
+}
+
+impl Evented for UnixStream {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
+
+/// A non-blocking Unix domain listening socket.
+pub struct UnixListener {
+    io: sys::Io,
+}
+
+impl NonBlock<UnixListener> {
+    pub fn accept(&self) -> io::Result<Option<NonBlock<UnixStream>>> {
+        let fd = unsafe {
+            libc::accept(self.as_raw_fd(), ::std::ptr::null_mut(), ::std::ptr::null_mut())
+        };
+
+        let fd = match try!(would_block(if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(fd)
+        })) {
+            Some(fd) => fd,
+            None => return Ok(None),
+        };
+
+        try!(net::set_nonblock(fd));
+        try!(net::set_cloexec(fd));
+
+        Ok(Some(NonBlock::new(unsafe { UnixStream::from_raw_fd(fd) })))
+    }
+}
+
+impl AsRawFd for UnixListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl Evented for UnixListener {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
+
+/// Binds a non-blocking Unix domain datagram socket at `path`, removing
+/// any stale socket file first.
+pub fn datagram(path: &Path) -> io::Result<NonBlock<UnixDatagram>> {
+    let _ = ::std::fs::remove_file(path);
+
+    let fd = try!(unix_socket(libc::SOCK_DGRAM));
+    let (addr, addr_len) = try!(to_sockaddr_un(path));
+
+    let ret = unsafe {
+        libc::bind(fd, &addr as *const _ as *const libc::sockaddr, addr_len)
+    };
+
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(NonBlock::new(UnixDatagram { io: sys::Io::new(fd) }))
+}
+
+/// A non-blocking Unix domain datagram socket.
+pub struct UnixDatagram {
+    io: sys::Io,
+}
+
+impl NonBlock<UnixDatagram> {
+    /// Sends the remainder of `buf` to `path`, returning `Ok(None)` if the
+    /// send would block, exactly like `udp::UdpSocket::send_to`.
+    pub fn send_to(&self, buf: &mut Buf, path: &Path) -> io::Result<Option<usize>> {
+        let (addr, addr_len) = try!(to_sockaddr_un(path));
+
+        let res = unsafe {
+            libc::sendto(self.as_raw_fd(),
+                         buf.bytes().as_ptr() as *const libc::c_void,
+                         buf.bytes().len(),
+                         0,
+                         &addr as *const _ as *const libc::sockaddr,
+                         addr_len)
+        };
+
+        let written = would_block(if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        });
+
+        if let Ok(Some(cnt)) = written {
+            buf.advance(cnt);
+        }
+
+        written
+    }
+
+    /// Fills `buf` with a single datagram, returning `Ok(None)` if none is
+    /// available yet, exactly like `udp::UdpSocket::recv_from`.
+    pub fn recv_from(&self, buf: &mut MutBuf) -> io::Result<Option<usize>> {
+        let dst = unsafe { buf.mut_bytes() };
+
+        let res = unsafe {
+            libc::recvfrom(self.as_raw_fd(),
+                           dst.as_mut_ptr() as *mut libc::c_void,
+                           dst.len(),
+                           0,
+                           ::std::ptr::null_mut(),
+                           ::std::ptr::null_mut())
+        };
+
+        let read = would_block(if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        });
+
+        if let Ok(Some(cnt)) = read {
+            unsafe { buf.advance(cnt); }
+        }
+
+        read
+    }
+}
+
+impl AsRawFd for UnixDatagram {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl Evented for UnixDatagram {
+    fn register(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.register(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn reregister(&self, selector: &mut sys::Selector, token: Token, interest: Interest, opts: PollOpt) -> io::Result<()> {
+        selector.reregister(self.as_raw_fd(), token, interest, opts)
+    }
+
+    fn deregister(&self, selector: &mut sys::Selector) -> io::Result<()> {
+        selector.deregister(self.as_raw_fd())
+    }
+}
@@ -0,0 +1,3 @@
+pub use self::slab::Slab;
+
+mod slab;
@@ -0,0 +1,110 @@
+use std::ops::{Index, IndexMut};
+
+use token::Token;
+
+enum Entry<T> {
+    Present(T),
+    Vacant(usize), // next free index
+}
+
+/// A fixed-capacity slab of values, indexed by `Token`, used to give
+/// connection state a stable identity across reregistrations without
+/// reaching for a `HashMap`. `insert` hands the value back once all
+/// `capacity` slots are taken; the slab never grows.
+pub struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    offset: usize,
+    next_free: usize,
+    count: usize,
+}
+
+impl<T> Slab<T> {
+    pub fn new(capacity: usize) -> Slab<T> {
+        Slab::new_starting_at(Token(0), capacity)
+    }
+
+    pub fn new_starting_at(offset: Token, capacity: usize) -> Slab<T> {
+        let mut entries = Vec::with_capacity(capacity);
+
+        for i in 0..capacity {
+            entries.push(Entry::Vacant(i + 1));
+        }
+
+        Slab {
+            entries: entries,
+            offset: offset.as_usize(),
+            next_free: 0,
+            count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn insert(&mut self, val: T) -> Result<Token, T> {
+        if self.next_free == self.entries.len() {
+            return Err(val);
+        }
+
+        let idx = self.next_free;
+
+        self.next_free = match self.entries[idx] {
+            Entry::Vacant(next) => next,
+            Entry::Present(_) => unreachable!(),
+        };
+
+        self.entries[idx] = Entry::Present(val);
+        self.count += 1;
+
+        Ok(Token(idx + self.offset))
+    }
+
+    pub fn remove(&mut self, token: Token) -> Option<T> {
+        let idx = token.as_usize() - self.offset;
+
+        if idx >= self.entries.len() {
+            return None;
+        }
+
+        match ::std::mem::replace(&mut self.entries[idx], Entry::Vacant(self.next_free)) {
+            Entry::Present(val) => {
+                self.next_free = idx;
+                self.count -= 1;
+                Some(val)
+            }
+            entry @ Entry::Vacant(_) => {
+                self.entries[idx] = entry;
+                None
+            }
+        }
+    }
+}
+
+impl<T> Index<Token> for Slab<T> {
+    type Output = T;
+
+    fn index(&self, token: Token) -> &T {
+        let idx = token.as_usize() - self.offset;
+
+        match self.entries[idx] {
+            Entry::Present(ref val) => val,
+            Entry::Vacant(_) => panic!("attempted to index a vacant slab entry"),
+        }
+    }
+}
+
+impl<T> IndexMut<Token> for Slab<T> {
+    fn index_mut(&mut self, token: Token) -> &mut T {
+        let idx = token.as_usize() - self.offset;
+
+        match self.entries[idx] {
+            Entry::Present(ref mut val) => val,
+            Entry::Vacant(_) => panic!("attempted to index a vacant slab entry"),
+        }
+    }
+}
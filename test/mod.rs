@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate log;
+extern crate mio;
+extern crate libc;
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod test_echo_server;
+mod test_readiness_cache;
+mod test_tcp_split;
+mod test_udp_socket;
+mod test_unix_socket;
+
+// Each test that binds a socket grabs its own port off this counter rather
+// than sharing one fixed address, since `cargo test` runs test functions (and
+// so these binds) concurrently.
+static NEXT_PORT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn localhost() -> SocketAddr {
+    let port = 18080 + NEXT_PORT.fetch_add(1, Ordering::SeqCst);
+    format!("127.0.0.1:{}", port).parse().unwrap()
+}
@@ -1,6 +1,6 @@
 use mio::*;
 use mio::tcp::*;
-use mio::buf::{ByteBuf, MutByteBuf, SliceBuf};
+use mio::buf::{Buf, ByteBuf, MutByteBuf, SliceBuf};
 use mio::util::Slab;
 use std::io;
 use super::localhost;
@@ -22,7 +22,7 @@ impl EchoConn {
             sock: sock,
             buf: None,
             mut_buf: Some(ByteBuf::mut_with_capacity(2048)),
-            token: Token(-1),
+            token: Token(usize::max_value()),
             interest: Interest::hup()
         }
     }
@@ -235,22 +235,31 @@ impl Handler for Echo {
     type Timeout = usize;
     type Message = ();
 
-    fn readable(&mut self, event_loop: &mut EventLoop<Echo>, token: Token, hint: ReadHint) {
-        assert!(hint.is_data());
-
-        match token {
-            SERVER => self.server.accept(event_loop).unwrap(),
-            CLIENT => self.client.readable(event_loop).unwrap(),
-            i => self.server.conn_readable(event_loop, i).unwrap()
-        };
-    }
-
-    fn writable(&mut self, event_loop: &mut EventLoop<Echo>, token: Token) {
+    fn ready(&mut self, event_loop: &mut EventLoop<Echo>, token: Token, events: EventSet) {
         match token {
-            SERVER => panic!("received writable for token 0"),
-            CLIENT => self.client.writable(event_loop).unwrap(),
-            _ => self.server.conn_writable(event_loop, token).unwrap()
-        };
+            SERVER => {
+                assert!(events.is_readable());
+                self.server.accept(event_loop).unwrap();
+            }
+            CLIENT => {
+                if events.is_readable() {
+                    self.client.readable(event_loop).unwrap();
+                }
+
+                if events.is_writable() {
+                    self.client.writable(event_loop).unwrap();
+                }
+            }
+            i => {
+                if events.is_readable() {
+                    self.server.conn_readable(event_loop, i).unwrap();
+                }
+
+                if events.is_writable() {
+                    self.server.conn_writable(event_loop, i).unwrap();
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,97 @@
+use mio::*;
+use mio::tcp::*;
+use super::localhost;
+
+const SERVER: Token = Token(0);
+
+struct AcceptOnce {
+    server: NonBlock<TcpListener>,
+    accepts: usize,
+}
+
+impl Handler for AcceptOnce {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, _event_loop: &mut EventLoop<AcceptOnce>, token: Token, events: EventSet) {
+        assert_eq!(token, SERVER);
+        assert!(events.is_readable());
+
+        if self.server.accept().unwrap().is_some() {
+            self.accepts += 1;
+        }
+    }
+}
+
+// A listening socket registered without `oneshot` is never reregistered by
+// its handler, so only the loop's own post-dispatch clearing narrows its
+// cached readiness. Dispatch follows real OS events (and `redeliver`), not
+// the cache, so the single real `accept`-ready event must not be replayed
+// on a later `run_once` with no second connection pending.
+#[test]
+pub fn test_readiness_cache_does_not_replay_stale_events() {
+    let mut event_loop = EventLoop::new().unwrap();
+
+    let addr = localhost();
+    let srv = tcp::v4().unwrap();
+    srv.set_reuseaddr(true).unwrap();
+    srv.bind(&addr).unwrap();
+    let srv = srv.listen(256).unwrap();
+
+    event_loop.register_opt(&srv, SERVER, Interest::readable(), PollOpt::edge()).unwrap();
+
+    // Connect exactly one client before the loop ever polls, so the first
+    // `run_once` sees a single real readable event for `SERVER`.
+    let _client = tcp::v4().unwrap().connect(&addr).unwrap();
+
+    let mut handler = AcceptOnce { server: srv, accepts: 0 };
+
+    event_loop.run_once(&mut handler, 1_000).unwrap();
+    assert_eq!(handler.accepts, 1);
+
+    // No new connection arrived; a short poll should find nothing, and the
+    // stale cached readiness must not cause a second synthetic dispatch.
+    event_loop.run_once(&mut handler, 50).unwrap();
+    assert_eq!(handler.accepts, 1);
+}
+
+struct ReadinessProbe {
+    server: NonBlock<TcpListener>,
+    seen_during_ready: EventSet,
+}
+
+impl Handler for ReadinessProbe {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<ReadinessProbe>, token: Token, _events: EventSet) {
+        assert_eq!(token, SERVER);
+        self.seen_during_ready = event_loop.readiness(token);
+        self.server.accept().unwrap();
+    }
+}
+
+// `readiness()` reflects real OS activity while a handler is still
+// processing it, but must not keep reporting already-delivered readiness
+// as "still outstanding" forever once the `ready` call has returned.
+#[test]
+pub fn test_readiness_cache_clears_once_delivered() {
+    let mut event_loop = EventLoop::new().unwrap();
+
+    let addr = localhost();
+    let srv = tcp::v4().unwrap();
+    srv.set_reuseaddr(true).unwrap();
+    srv.bind(&addr).unwrap();
+    let srv = srv.listen(256).unwrap();
+
+    event_loop.register_opt(&srv, SERVER, Interest::readable(), PollOpt::edge()).unwrap();
+
+    let _client = tcp::v4().unwrap().connect(&addr).unwrap();
+
+    let mut handler = ReadinessProbe { server: srv, seen_during_ready: EventSet::none() };
+
+    event_loop.run_once(&mut handler, 1_000).unwrap();
+
+    assert!(handler.seen_during_ready.is_readable());
+    assert!(event_loop.readiness(SERVER).is_none());
+}
@@ -0,0 +1,193 @@
+use mio::*;
+use mio::tcp::*;
+use mio::buf::{Buf, ByteBuf, SliceBuf};
+use super::localhost;
+
+// Both halves of a split stream still share a single underlying fd, so
+// `epoll` can only ever hold one registration for it; both halves must be
+// registered/reregistered against the same `Token` (one `register_opt` to
+// create the registration, any further interest changes going through
+// `reregister`) rather than each other under their own tokens.
+const CONN: Token = Token(0);
+
+struct SplitClient {
+    read: ReadHalf,
+    write: WriteHalf,
+    out: SliceBuf<'static>,
+    wrote: bool,
+    reply: Vec<u8>,
+}
+
+impl Handler for SplitClient {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<SplitClient>, token: Token, events: EventSet) {
+        assert_eq!(token, CONN);
+
+        if events.is_writable() && !self.wrote {
+            self.write.write(&mut self.out).unwrap();
+            self.wrote = true;
+
+            // Narrow `write`'s own interest to none. If the two halves
+            // didn't union their interest into one shared registration,
+            // this would also silence `read`'s still-live readable
+            // interest on the same fd/token.
+            event_loop.reregister(&self.write, CONN, Interest::none(), PollOpt::edge()).unwrap();
+        }
+
+        if events.is_readable() {
+            let mut buf = ByteBuf::mut_with_capacity(16);
+
+            if let Some(n) = self.read.read(&mut buf).unwrap() {
+                assert!(n > 0);
+                self.reply.extend_from_slice(buf.flip().bytes());
+                event_loop.shutdown();
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_tcp_split_halves_register_independently() {
+    let addr = localhost();
+    let srv = tcp::v4().unwrap();
+    srv.set_reuseaddr(true).unwrap();
+    srv.bind(&addr).unwrap();
+    let srv = srv.listen(256).unwrap();
+
+    let (sock, _) = tcp::v4().unwrap().connect(&addr).unwrap();
+
+    // Accept and echo from the server side without going through an
+    // `EventLoop` at all; this test is only about the client-side split.
+    let mut peer = loop {
+        if let Some(peer) = srv.accept().unwrap() {
+            break peer;
+        }
+    };
+
+    let (read, write) = sock.split();
+
+    let mut event_loop = EventLoop::new().unwrap();
+    event_loop.register_opt(&read, CONN, Interest::readable(), PollOpt::edge()).unwrap();
+    event_loop.reregister(&write, CONN, Interest::writable(), PollOpt::edge()).unwrap();
+
+    let mut handler = SplitClient {
+        read: read,
+        write: write,
+        out: SliceBuf::wrap(b"ping"),
+        wrote: false,
+        reply: Vec::new(),
+    };
+
+    while !handler.wrote {
+        event_loop.run_once(&mut handler, 1_000).unwrap();
+    }
+
+    let mut sent = ByteBuf::mut_with_capacity(16);
+
+    loop {
+        if let Some(n) = peer.read(&mut sent).unwrap() {
+            assert!(n > 0);
+            break;
+        }
+    }
+
+    let mut sent = sent.flip();
+    peer.write(&mut sent).unwrap();
+
+    while handler.reply.is_empty() {
+        event_loop.run_once(&mut handler, 1_000).unwrap();
+    }
+
+    assert_eq!(handler.reply, b"ping");
+}
+
+struct SplitMutClient<'a> {
+    read: ReadHalfMut<'a>,
+    write: WriteHalfMut<'a>,
+    out: SliceBuf<'static>,
+    wrote: bool,
+    reply: Vec<u8>,
+}
+
+impl<'a> Handler for SplitMutClient<'a> {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<SplitMutClient<'a>>, token: Token, events: EventSet) {
+        assert_eq!(token, CONN);
+
+        if events.is_writable() && !self.wrote {
+            self.write.write_slice(self.out.bytes()).unwrap();
+            self.wrote = true;
+
+            // Same clobbering check as the owned-split test, against
+            // `split_mut`'s separately-bookkept `SplitMutShared`.
+            event_loop.reregister(&self.write, CONN, Interest::none(), PollOpt::edge()).unwrap();
+        }
+
+        if events.is_readable() {
+            let mut buf = [0u8; 16];
+
+            if let Some(n) = self.read.read_slice(&mut buf).unwrap() {
+                assert!(n > 0);
+                self.reply.extend_from_slice(&buf[..n]);
+                event_loop.shutdown();
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_tcp_split_mut_halves_register_independently() {
+    let addr = localhost();
+    let srv = tcp::v4().unwrap();
+    srv.set_reuseaddr(true).unwrap();
+    srv.bind(&addr).unwrap();
+    let srv = srv.listen(256).unwrap();
+
+    let (mut sock, _) = tcp::v4().unwrap().connect(&addr).unwrap();
+
+    let mut peer = loop {
+        if let Some(peer) = srv.accept().unwrap() {
+            break peer;
+        }
+    };
+
+    let (read, write) = sock.split_mut();
+
+    let mut event_loop = EventLoop::new().unwrap();
+    event_loop.register_opt(&read, CONN, Interest::readable(), PollOpt::edge()).unwrap();
+    event_loop.reregister(&write, CONN, Interest::writable(), PollOpt::edge()).unwrap();
+
+    let mut handler = SplitMutClient {
+        read: read,
+        write: write,
+        out: SliceBuf::wrap(b"ping"),
+        wrote: false,
+        reply: Vec::new(),
+    };
+
+    while !handler.wrote {
+        event_loop.run_once(&mut handler, 1_000).unwrap();
+    }
+
+    let mut sent = ByteBuf::mut_with_capacity(16);
+
+    loop {
+        if let Some(n) = peer.read(&mut sent).unwrap() {
+            assert!(n > 0);
+            break;
+        }
+    }
+
+    let mut sent = sent.flip();
+    peer.write(&mut sent).unwrap();
+
+    while handler.reply.is_empty() {
+        event_loop.run_once(&mut handler, 1_000).unwrap();
+    }
+
+    assert_eq!(handler.reply, b"ping");
+}
@@ -0,0 +1,75 @@
+use mio::*;
+use mio::udp::*;
+use mio::buf::{Buf, ByteBuf, SliceBuf};
+use super::localhost;
+
+const LISTENER: Token = Token(0);
+const SENDER: Token = Token(1);
+
+struct UdpHandler {
+    tx: NonBlock<UdpSocket>,
+    rx: NonBlock<UdpSocket>,
+    msg: &'static str,
+    buf: SliceBuf<'static>,
+    rx_buf: Option<ByteBuf>,
+}
+
+impl UdpHandler {
+    fn new(tx: NonBlock<UdpSocket>, rx: NonBlock<UdpSocket>, msg: &'static str) -> UdpHandler {
+        UdpHandler {
+            tx: tx,
+            rx: rx,
+            msg: msg,
+            buf: SliceBuf::wrap(msg.as_bytes()),
+            rx_buf: Some(ByteBuf::mut_with_capacity(1024).flip()),
+        }
+    }
+}
+
+impl Handler for UdpHandler {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<UdpHandler>, token: Token, events: EventSet) {
+        match token {
+            SENDER => {
+                assert!(events.is_writable());
+
+                let addr = self.rx.local_addr().unwrap();
+                self.tx.send_to(&mut self.buf, &addr).unwrap();
+            }
+            LISTENER => {
+                assert!(events.is_readable());
+
+                let mut buf = self.rx_buf.take().unwrap().flip();
+                self.rx.recv_from(&mut buf).unwrap();
+
+                let received = buf.flip();
+                assert_eq!(received.bytes(), self.msg.as_bytes());
+
+                event_loop.shutdown();
+            }
+            _ => panic!("unexpected token"),
+        }
+    }
+}
+
+#[test]
+pub fn test_udp_socket() {
+    let mut event_loop = EventLoop::new().unwrap();
+
+    let addr = localhost();
+    let any = "127.0.0.1:0".parse().unwrap();
+
+    let rx = udp::v4().unwrap();
+    rx.bind(&addr).unwrap();
+
+    let tx = udp::v4().unwrap();
+    tx.bind(&any).unwrap();
+
+    event_loop.register_opt(&rx, LISTENER, Interest::readable(), PollOpt::edge() | PollOpt::oneshot()).unwrap();
+    event_loop.register_opt(&tx, SENDER, Interest::writable(), PollOpt::edge() | PollOpt::oneshot()).unwrap();
+
+    let mut handler = UdpHandler::new(tx, rx, "hello datagram world");
+    event_loop.run(&mut handler).unwrap();
+}
@@ -0,0 +1,149 @@
+use mio::*;
+use mio::unix::*;
+use mio::buf::{Buf, ByteBuf, SliceBuf};
+use std::path::PathBuf;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+const SERVER: Token = Token(0);
+const CLIENT: Token = Token(1);
+
+fn socket_path() -> PathBuf {
+    PathBuf::from(format!("/tmp/mio-test-unix-{}.sock", unsafe { libc::getpid() }))
+}
+
+fn datagram_path(suffix: &str) -> PathBuf {
+    PathBuf::from(format!("/tmp/mio-test-unix-dgram-{}-{}.sock", unsafe { libc::getpid() }, suffix))
+}
+
+struct FdPassing {
+    server: NonBlock<UnixListener>,
+    client: NonBlock<UnixStream>,
+    peer: Option<NonBlock<UnixStream>>,
+    passed_fd: RawFd,
+    done: bool,
+}
+
+impl Handler for FdPassing {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<FdPassing>, token: Token, events: EventSet) {
+        match token {
+            SERVER => {
+                assert!(events.is_readable());
+                let peer = self.server.accept().unwrap().unwrap();
+
+                // Hand the listening socket's own fd across as the payload;
+                // a real server would pass a connected worker fd instead.
+                peer.send_fd(self.server.as_raw_fd()).unwrap();
+                self.peer = Some(peer);
+            }
+            CLIENT => {
+                assert!(events.is_readable());
+
+                if let Some(fd) = self.client.recv_fd().unwrap() {
+                    self.passed_fd = fd;
+                    self.done = true;
+                    event_loop.shutdown();
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+pub fn test_unix_socket_fd_passing() {
+    let path = socket_path();
+    let mut event_loop = EventLoop::new().unwrap();
+
+    let server = unix::bind(&path).unwrap();
+    event_loop.register_opt(&server, SERVER, Interest::readable(), PollOpt::edge()).unwrap();
+
+    let client = unix::connect(&path).unwrap();
+    event_loop.register_opt(&client, CLIENT, Interest::readable(), PollOpt::edge()).unwrap();
+
+    let mut handler = FdPassing {
+        server: server,
+        client: client,
+        peer: None,
+        passed_fd: -1 as RawFd,
+        done: false,
+    };
+
+    event_loop.run(&mut handler).unwrap();
+
+    assert!(handler.done);
+    assert!(handler.passed_fd >= 0);
+
+    unsafe { libc::close(handler.passed_fd); }
+    let _ = ::std::fs::remove_file(&path);
+}
+
+struct DatagramEcho {
+    tx: NonBlock<UnixDatagram>,
+    rx: NonBlock<UnixDatagram>,
+    rx_path: PathBuf,
+    msg: &'static str,
+    buf: SliceBuf<'static>,
+    rx_buf: Option<ByteBuf>,
+    received: bool,
+}
+
+impl Handler for DatagramEcho {
+    type Timeout = usize;
+    type Message = ();
+
+    fn ready(&mut self, event_loop: &mut EventLoop<DatagramEcho>, token: Token, events: EventSet) {
+        match token {
+            CLIENT => {
+                assert!(events.is_writable());
+                self.tx.send_to(&mut self.buf, &self.rx_path).unwrap();
+            }
+            SERVER => {
+                assert!(events.is_readable());
+
+                let mut buf = self.rx_buf.take().unwrap().flip();
+                self.rx.recv_from(&mut buf).unwrap();
+
+                let received = buf.flip();
+                assert_eq!(received.bytes(), self.msg.as_bytes());
+
+                self.received = true;
+                event_loop.shutdown();
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+pub fn test_unix_socket_datagram() {
+    let mut event_loop = EventLoop::new().unwrap();
+
+    let rx_path = datagram_path("rx");
+    let tx_path = datagram_path("tx");
+
+    let rx = unix::datagram(&rx_path).unwrap();
+    let tx = unix::datagram(&tx_path).unwrap();
+
+    event_loop.register_opt(&rx, SERVER, Interest::readable(), PollOpt::edge() | PollOpt::oneshot()).unwrap();
+    event_loop.register_opt(&tx, CLIENT, Interest::writable(), PollOpt::edge() | PollOpt::oneshot()).unwrap();
+
+    let mut handler = DatagramEcho {
+        tx: tx,
+        rx: rx,
+        rx_path: rx_path.clone(),
+        msg: "hello datagram world",
+        buf: SliceBuf::wrap(b"hello datagram world"),
+        rx_buf: Some(ByteBuf::mut_with_capacity(1024).flip()),
+        received: false,
+    };
+
+    event_loop.run(&mut handler).unwrap();
+
+    assert!(handler.received);
+
+    let _ = ::std::fs::remove_file(&rx_path);
+    let _ = ::std::fs::remove_file(&tx_path);
+}